@@ -23,6 +23,9 @@ pub struct BlockData {
     /// Successors of this block.
     successors: HashSet<BlockEdge>,
 
+    /// Predecessors of this block.
+    predecessors: HashSet<BlockEdge>,
+
     /// The first instructions in the block.
     head: Option<Inst>,
     /// The last instruction in the block.
@@ -48,6 +51,7 @@ impl Block {
             next: None,
             prev: None,
             successors: HashSet::new(),
+            predecessors: HashSet::new(),
             head: None,
             tail: None,
             container: None,
@@ -65,11 +69,32 @@ impl Block {
         DisplayBlock { ctx, block: self }
     }
 
-    pub fn remove(self, ctx: &mut Context, edges: &Vec<BlockEdge>) {
+    /// Remove this block from its function, scrubbing its edges from both
+    /// neighbors. Only safe one block at a time: batch-removing a connected
+    /// group needs `remove_predecessor_edge`/`remove_successor_edge` instead,
+    /// since a neighbor here may already be gone.
+    pub fn remove(self, ctx: &mut Context) {
+        for edge in self.deref(ctx).successors.clone() {
+            edge.to().remove_predecessor_edge(ctx, self, edge.inst(), edge.is_true_branch());
+        }
+        for edge in self.deref(ctx).predecessors.clone() {
+            edge.to().remove_successor_edge(ctx, self, edge.inst(), edge.is_true_branch());
+        }
+
         let container = self.container(ctx).unwrap();
         container.remove_block(ctx, self);
     }
 
+    /// Drop a predecessor entry without touching its source.
+    pub(crate) fn remove_predecessor_edge(self, ctx: &mut Context, from: Block, inst: Inst, true_br: bool) {
+        self.deref_mut(ctx).predecessors.remove(&BlockEdge(from, inst, true_br));
+    }
+
+    /// Drop a successor entry without touching its target.
+    pub(crate) fn remove_successor_edge(self, ctx: &mut Context, to: Block, inst: Inst, true_br: bool) {
+        self.deref_mut(ctx).successors.remove(&BlockEdge(to, inst, true_br));
+    }
+
     /// Remove inst in the block.
     pub fn remove_inst(self, ctx: &mut Context, inst: Inst) {
         let mut head = self.head(ctx);
@@ -110,6 +135,10 @@ impl Block {
             }
         };
         self.deref_mut(ctx).successors.insert(edge);
+        successor
+            .deref_mut(ctx)
+            .predecessors
+            .insert(BlockEdge(self, edge.inst(), edge.is_true_branch()));
     }
 
     pub fn remove_successor(self, ctx: &mut Context, successor: Block, inst: Inst, true_br: bool) {
@@ -129,6 +158,14 @@ impl Block {
                 self.deref_mut(ctx)
                     .successors
                     .remove(&BlockEdge(new_succ, inst, !true_br));
+                successor
+                    .deref_mut(ctx)
+                    .predecessors
+                    .remove(&BlockEdge(self, inst, true_br));
+                new_succ
+                    .deref_mut(ctx)
+                    .predecessors
+                    .remove(&BlockEdge(self, inst, !true_br));
 
                 let new_inst = Inst::br(ctx, new_succ);
                 inst.insert_after(ctx, new_inst).unwrap(); // 先加结点后删结点，避免处理边界条件
@@ -145,19 +182,55 @@ impl Block {
         };
         inst.remove(ctx);
         self.deref_mut(ctx).successors.remove(&edge);
+        successor
+            .deref_mut(ctx)
+            .predecessors
+            .remove(&BlockEdge(self, edge.inst(), edge.is_true_branch()));
     }
 
     pub fn clear_successors(self, ctx: &mut Context) {
+        for edge in self.deref(ctx).successors.clone() {
+            edge.to()
+                .deref_mut(ctx)
+                .predecessors
+                .remove(&BlockEdge(self, edge.inst(), edge.is_true_branch()));
+        }
         self.deref_mut(ctx).successors.clear();
     }
 
     pub fn copy_successors(self, ctx: &mut Context, successors: HashSet<BlockEdge>) {
+        for edge in self.deref(ctx).successors.clone() {
+            edge.to()
+                .deref_mut(ctx)
+                .predecessors
+                .remove(&BlockEdge(self, edge.inst(), edge.is_true_branch()));
+        }
+        for edge in &successors {
+            edge.to()
+                .deref_mut(ctx)
+                .predecessors
+                .insert(BlockEdge(self, edge.inst(), edge.is_true_branch()));
+        }
         self.deref_mut(ctx).successors = successors;
     }
 
     pub fn successors(self, ctx: &Context) -> &HashSet<BlockEdge> {
         &self.deref(ctx).successors
     }
+
+    /// Blocks that branch directly to this block.
+    pub fn predecessors(self, ctx: &Context) -> &HashSet<BlockEdge> {
+        &self.deref(ctx).predecessors
+    }
+
+    /// The branch flags of the edges from `source` to `self`.
+    pub fn switch_sources(self, ctx: &Context, source: Block) -> HashSet<bool> {
+        self.predecessors(ctx)
+            .iter()
+            .filter(|edge| edge.to() == source)
+            .map(|edge| edge.is_true_branch())
+            .collect()
+    }
 }
 
 impl fmt::Display for DisplayBlock<'_> {
@@ -172,6 +245,49 @@ impl fmt::Display for DisplayBlock<'_> {
     }
 }
 
+/// Renders a function's CFG as a Graphviz DOT digraph.
+pub struct DisplayCfg<'ctx> {
+    ctx: &'ctx Context,
+    func: Func,
+}
+
+impl Func {
+    pub fn display_cfg(self, ctx: &Context) -> DisplayCfg {
+        DisplayCfg { ctx, func: self }
+    }
+}
+
+impl fmt::Display for DisplayCfg<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "digraph cfg {{")?;
+
+        for block in self.func.iter(self.ctx) {
+            let label = block
+                .display(self.ctx)
+                .to_string()
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\l");
+            writeln!(f, "    bb_{} [shape=box, label=\"{}\\l\"];", block.0.index(), label)?;
+
+            for edge in block.successors(self.ctx) {
+                let edge_label = match edge.inst().kind(self.ctx) {
+                    InstKind::CondBr => Some(if edge.is_true_branch() { "true" } else { "false" }),
+                    _ => None,
+                };
+
+                write!(f, "    bb_{} -> bb_{}", block.0.index(), edge.to().0.index())?;
+                if let Some(edge_label) = edge_label {
+                    write!(f, " [label=\"{edge_label}\"]")?;
+                }
+                writeln!(f, ";")?;
+            }
+        }
+
+        writeln!(f, "}}")
+    }
+}
+
 impl ArenaPtr for Block {
     type Arena = Context;
     type Data = BlockData;
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use super::block::Block;
+use super::context::Context;
+
+/// The dominator tree of a function's control-flow graph, computed with the
+/// Cooper-Harvey-Kennedy "simple, fast dominance" algorithm.
+pub struct Dominators {
+    /// RPO number of each block reachable from the entry.
+    rpo_number: HashMap<Block, usize>,
+    /// Blocks in reverse postorder, indexed by RPO number.
+    rpo: Vec<Block>,
+    /// Immediate dominator of each block, indexed by RPO number.
+    idom: Vec<usize>,
+}
+
+impl Dominators {
+    /// Compute the dominator tree of the CFG reachable from `entry`.
+    pub fn compute(ctx: &Context, entry: Block) -> Self {
+        let rpo: Vec<Block> = entry.reverse_postorder(ctx).collect();
+        let rpo_number: HashMap<Block, usize> =
+            rpo.iter().enumerate().map(|(i, &block)| (block, i)).collect();
+
+        // `idom[0]`, the entry, dominates itself; everything else starts
+        // undefined until its first processed predecessor is found.
+        let mut idom = vec![usize::MAX; rpo.len()];
+        idom[0] = 0;
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for i in 1..rpo.len() {
+                let mut new_idom = None;
+
+                for edge in rpo[i].predecessors(ctx) {
+                    let Some(&p) = rpo_number.get(&edge.to()) else {
+                        // Predecessor is unreachable from the entry.
+                        continue;
+                    };
+                    if idom[p] == usize::MAX {
+                        // Not yet processed this round.
+                        continue;
+                    }
+                    new_idom = Some(match new_idom {
+                        None => p,
+                        Some(curr) => Self::intersect(&idom, curr, p),
+                    });
+                }
+
+                if let Some(new_idom) = new_idom {
+                    if idom[i] != new_idom {
+                        idom[i] = new_idom;
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        Self { rpo_number, rpo, idom }
+    }
+
+    /// Walk two fingers up the idom chain until they meet.
+    fn intersect(idom: &[usize], mut a: usize, mut b: usize) -> usize {
+        while a != b {
+            while a > b {
+                a = idom[a];
+            }
+            while b > a {
+                b = idom[b];
+            }
+        }
+        a
+    }
+
+    /// The immediate dominator of `block`, or `None` if unreachable.
+    pub fn idom(&self, block: Block) -> Option<Block> {
+        let &i = self.rpo_number.get(&block)?;
+        Some(self.rpo[self.idom[i]])
+    }
+
+    /// Whether every path from the entry to `b` passes through `a`.
+    pub fn dominates(&self, a: Block, b: Block) -> bool {
+        let Some(&ia) = self.rpo_number.get(&a) else {
+            return false;
+        };
+        let Some(&ib) = self.rpo_number.get(&b) else {
+            return false;
+        };
+
+        let mut cur = ib;
+        loop {
+            if cur == ia {
+                return true;
+            }
+            if cur == self.idom[cur] {
+                // Reached the entry (self-idom) without passing through `a`.
+                return false;
+            }
+            cur = self.idom[cur];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::inst::Inst;
+
+    fn br_edge(ctx: &mut Context, from: Block, to: Block) {
+        let inst = Inst::br(ctx, to);
+        from.add_successor(ctx, to, inst, false);
+    }
+
+    #[test]
+    fn diamond_dominance() {
+        let mut ctx = Context::default();
+        let entry = Block::new(&mut ctx);
+        let left = Block::new(&mut ctx);
+        let right = Block::new(&mut ctx);
+        let join = Block::new(&mut ctx);
+
+        br_edge(&mut ctx, entry, left);
+        br_edge(&mut ctx, entry, right);
+        br_edge(&mut ctx, left, join);
+        br_edge(&mut ctx, right, join);
+
+        let doms = Dominators::compute(&ctx, entry);
+
+        assert_eq!(doms.idom(entry), Some(entry));
+        assert_eq!(doms.idom(left), Some(entry));
+        assert_eq!(doms.idom(right), Some(entry));
+        assert_eq!(doms.idom(join), Some(entry));
+
+        assert!(doms.dominates(entry, join));
+        assert!(!doms.dominates(left, join));
+        assert!(!doms.dominates(right, join));
+    }
+
+    #[test]
+    fn loop_back_edge_reconverges() {
+        let mut ctx = Context::default();
+        let entry = Block::new(&mut ctx);
+        let header = Block::new(&mut ctx);
+        let body = Block::new(&mut ctx);
+        let exit = Block::new(&mut ctx);
+
+        br_edge(&mut ctx, entry, header);
+        br_edge(&mut ctx, header, body);
+        br_edge(&mut ctx, body, header);
+        br_edge(&mut ctx, header, exit);
+
+        let doms = Dominators::compute(&ctx, entry);
+
+        assert_eq!(doms.idom(header), Some(entry));
+        assert_eq!(doms.idom(body), Some(header));
+        assert_eq!(doms.idom(exit), Some(header));
+    }
+
+    #[test]
+    fn unreachable_block_has_no_idom() {
+        let mut ctx = Context::default();
+        let entry = Block::new(&mut ctx);
+        let unreachable = Block::new(&mut ctx);
+
+        let doms = Dominators::compute(&ctx, entry);
+
+        assert_eq!(doms.idom(unreachable), None);
+        assert!(!doms.dominates(entry, unreachable));
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+use std::vec;
+
+use super::block::Block;
+use super::context::Context;
+use super::func::Func;
+use crate::infra::linked_list::LinkedListContainer;
+
+/// Which edges a traversal follows: successor edges for analyses that flow
+/// with control flow, or predecessor edges for analyses that flow against
+/// it (e.g. backward dataflow).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Forward,
+    Backward,
+}
+
+impl Direction {
+    fn neighbors(self, ctx: &Context, block: Block) -> Vec<Block> {
+        match self {
+            Direction::Forward => block.successors(ctx).iter().map(|edge| edge.to()).collect(),
+            Direction::Backward => block.predecessors(ctx).iter().map(|edge| edge.to()).collect(),
+        }
+    }
+}
+
+/// DFS preorder: a block is yielded the moment it is first discovered.
+pub struct DfsPreorder<'ctx> {
+    ctx: &'ctx Context,
+    direction: Direction,
+    visited: HashSet<Block>,
+    stack: Vec<Block>,
+}
+
+impl<'ctx> DfsPreorder<'ctx> {
+    fn new(ctx: &'ctx Context, start: Block, direction: Direction) -> Self {
+        Self { ctx, direction, visited: HashSet::new(), stack: vec![start] }
+    }
+}
+
+impl Iterator for DfsPreorder<'_> {
+    type Item = Block;
+
+    fn next(&mut self) -> Option<Block> {
+        while let Some(block) = self.stack.pop() {
+            if !self.visited.insert(block) {
+                // Already reached via a deeper path.
+                continue;
+            }
+            for succ in self.direction.neighbors(self.ctx, block) {
+                if !self.visited.contains(&succ) {
+                    self.stack.push(succ);
+                }
+            }
+            return Some(block);
+        }
+        None
+    }
+}
+
+/// DFS postorder: a block is yielded only once all of its (unvisited)
+/// neighbors have been yielded.
+pub struct DfsPostorder<'ctx> {
+    ctx: &'ctx Context,
+    direction: Direction,
+    visited: HashSet<Block>,
+    stack: Vec<(Block, bool)>,
+}
+
+impl<'ctx> DfsPostorder<'ctx> {
+    fn new(ctx: &'ctx Context, start: Block, direction: Direction) -> Self {
+        Self { ctx, direction, visited: HashSet::new(), stack: vec![(start, false)] }
+    }
+}
+
+impl Iterator for DfsPostorder<'_> {
+    type Item = Block;
+
+    fn next(&mut self) -> Option<Block> {
+        while let Some((block, expanded)) = self.stack.pop() {
+            if expanded {
+                return Some(block);
+            }
+            if !self.visited.insert(block) {
+                // Already expanded via a deeper path.
+                continue;
+            }
+            self.stack.push((block, true));
+            for succ in self.direction.neighbors(self.ctx, block) {
+                if !self.visited.contains(&succ) {
+                    self.stack.push((succ, false));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Reverse postorder, precomputed: unlike preorder/postorder this cannot be
+/// yielded incrementally, since the first block it yields (the start block)
+/// is the last one the underlying postorder walk discovers.
+pub struct ReversePostorder {
+    blocks: vec::IntoIter<Block>,
+}
+
+impl Iterator for ReversePostorder {
+    type Item = Block;
+
+    fn next(&mut self) -> Option<Block> {
+        self.blocks.next()
+    }
+}
+
+impl Block {
+    /// DFS preorder over blocks reachable from `self` via successor edges.
+    pub fn dfs_preorder(self, ctx: &Context) -> DfsPreorder {
+        DfsPreorder::new(ctx, self, Direction::Forward)
+    }
+
+    /// DFS preorder over blocks that can reach `self`, walking predecessor
+    /// edges backward against control flow.
+    pub fn dfs_preorder_rev(self, ctx: &Context) -> DfsPreorder {
+        DfsPreorder::new(ctx, self, Direction::Backward)
+    }
+
+    /// DFS postorder over blocks reachable from `self` via successor edges.
+    pub fn postorder(self, ctx: &Context) -> DfsPostorder {
+        DfsPostorder::new(ctx, self, Direction::Forward)
+    }
+
+    /// DFS postorder over blocks that can reach `self`, walking predecessor
+    /// edges backward against control flow.
+    pub fn postorder_rev(self, ctx: &Context) -> DfsPostorder {
+        DfsPostorder::new(ctx, self, Direction::Backward)
+    }
+
+    /// Reverse postorder over blocks reachable from `self`: the canonical
+    /// ordering for forward dataflow analyses.
+    pub fn reverse_postorder(self, ctx: &Context) -> ReversePostorder {
+        let mut blocks: Vec<Block> = self.postorder(ctx).collect();
+        blocks.reverse();
+        ReversePostorder { blocks: blocks.into_iter() }
+    }
+}
+
+impl Func {
+    /// DFS preorder over the function's blocks, starting at the entry.
+    pub fn dfs_preorder(self, ctx: &Context) -> DfsPreorder {
+        self.head(ctx).expect("function has no entry block").dfs_preorder(ctx)
+    }
+
+    /// DFS postorder over the function's blocks, starting at the entry.
+    pub fn postorder(self, ctx: &Context) -> DfsPostorder {
+        self.head(ctx).expect("function has no entry block").postorder(ctx)
+    }
+
+    /// Reverse postorder over the function's blocks, starting at the entry:
+    /// the canonical ordering for forward dataflow analyses.
+    pub fn reverse_postorder(self, ctx: &Context) -> ReversePostorder {
+        self.head(ctx).expect("function has no entry block").reverse_postorder(ctx)
+    }
+}